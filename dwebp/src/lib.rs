@@ -1,7 +1,14 @@
-use std::{cmp::max, error, fmt};
-
-use image::{codecs::png::PngEncoder, EncodableLayout as _, ImageEncoder as _, Pixel, Rgba};
-// use image::{DynamicImage, ImageOutputFormat};
+use std::{cmp::max, error, fmt, ptr, slice, time::Duration};
+
+use image::{
+    codecs::{
+        gif::{GifEncoder, Repeat},
+        jpeg::JpegEncoder,
+        png::PngEncoder,
+    },
+    DynamicImage, EncodableLayout as _, Delay, Frame, ImageEncoder as _, Pixel, Rgba,
+};
+use libwebp_sys as sys;
 use webp_animation::Decoder as AwebPDecoder;
 
 //
@@ -22,11 +29,29 @@ impl Default for AwebpFramePosition {
 pub fn awebp_to_single_png(
     awebp_bytes: impl AsRef<[u8]>,
     frame_position: impl Into<Option<AwebpFramePosition>>,
+    composite: impl Into<Option<AwebpCompositeOptions>>,
 ) -> Result<Vec<u8>, AwebpToPngError> {
+    awebp_to_image(awebp_bytes, frame_position, composite, OutputFormat::Png).map_err(Into::into)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutputFormat {
+    Png,
+    Jpeg { quality: u8 },
+    WebP { lossless: bool, quality: f32 },
+}
+
+pub fn awebp_to_image(
+    awebp_bytes: impl AsRef<[u8]>,
+    frame_position: impl Into<Option<AwebpFramePosition>>,
+    composite: impl Into<Option<AwebpCompositeOptions>>,
+    output_format: OutputFormat,
+) -> Result<Vec<u8>, AwebpToImageError> {
     let frame_position: AwebpFramePosition = frame_position.into().unwrap_or_default();
+    let composite: AwebpCompositeOptions = composite.into().unwrap_or_default();
 
     let awebp_decoder =
-        AwebPDecoder::new(awebp_bytes.as_ref()).map_err(|_| AwebpToPngError::DecodeAwebpFailed)?;
+        AwebPDecoder::new(awebp_bytes.as_ref()).map_err(|_| AwebpToImageError::DecodeAwebpFailed)?;
 
     let awebp_decoder_iter = awebp_decoder.into_iter();
 
@@ -35,7 +60,7 @@ pub fn awebp_to_single_png(
             awebp_decoder_iter
                 .enumerate()
                 .find(|(i, _)| *i == 0)
-                .ok_or(AwebpToPngError::AwebpSpecificFrameIsNone)?
+                .ok_or(AwebpToImageError::AwebpSpecificFrameIsNone)?
                 .1
         }
         AwebpFramePosition::Specific(n) => {
@@ -44,51 +69,125 @@ pub fn awebp_to_single_png(
             awebp_decoder_iter
                 .enumerate()
                 .find(|(i, _)| *i == n - 1)
-                .ok_or(AwebpToPngError::AwebpSpecificFrameIsNone)?
+                .ok_or(AwebpToImageError::AwebpSpecificFrameIsNone)?
                 .1
         }
         AwebpFramePosition::Last => awebp_decoder_iter
             .last()
-            .ok_or(AwebpToPngError::AwebpSpecificFrameIsNone)?,
+            .ok_or(AwebpToImageError::AwebpSpecificFrameIsNone)?,
     };
 
     let image = webp_frame
         .into_image()
-        .map_err(|_| AwebpToPngError::ToImageFailed)?;
+        .map_err(|_| AwebpToImageError::ToImageFailed)?;
 
-    // https://github.com/image-rs/image/blob/v0.23.14/src/buffer.rs#L926
-    // https://github.com/image-rs/image/blob/v0.23.14/src/dynimage.rs#L1280
-    // https://github.com/image-rs/image/blob/v0.23.14/src/io/free_functions.rs#L174
+    let image = match composite.background_color_override {
+        Some(background_color) => apply_background_color_override(image, background_color),
+        None => image,
+    };
 
-    let mut buf = Vec::with_capacity(image.as_bytes().len());
+    match output_format {
+        OutputFormat::Png => {
+            let mut buf = Vec::with_capacity(image.as_bytes().len());
 
-    // DynamicImage::ImageRgba8(image)
-    //     .write_to(&mut buf, ImageOutputFormat::Png)
-    //     .map_err(|_| AwebpToPngError::EncodePngFailed)?;
+            PngEncoder::new(&mut buf)
+                .write_image(
+                    image.as_bytes(),
+                    image.width(),
+                    image.height(),
+                    Rgba::<u8>::COLOR_TYPE,
+                )
+                .map_err(|_| AwebpToImageError::EncodeImageFailed)?;
 
-    PngEncoder::new(&mut buf)
-        .write_image(
-            image.as_bytes(),
-            image.width(),
-            image.height(),
-            Rgba::<u8>::COLOR_TYPE,
-        )
-        .map_err(|_| AwebpToPngError::EncodePngFailed)?;
+            Ok(buf)
+        }
+        OutputFormat::Jpeg { quality } => {
+            // JPEG has no alpha channel, so the composited/decoded canvas is
+            // flattened onto its own RGB channels first.
+            let rgb_image = DynamicImage::ImageRgba8(image).into_rgb8();
+
+            let mut buf = Vec::new();
+
+            JpegEncoder::new_with_quality(&mut buf, quality)
+                .write_image(
+                    rgb_image.as_bytes(),
+                    rgb_image.width(),
+                    rgb_image.height(),
+                    image::ColorType::Rgb8,
+                )
+                .map_err(|_| AwebpToImageError::EncodeImageFailed)?;
+
+            Ok(buf)
+        }
+        OutputFormat::WebP { lossless, quality } => {
+            if !(0. ..=100.).contains(&quality) {
+                return Err(AwebpToImageError::QualityOutOfRange);
+            }
 
-    Ok(buf)
+            encode_rgba_to_webp(&image, lossless, quality)
+        }
+    }
 }
 
-pub fn awebp_to_multi_png(awebp_bytes: impl AsRef<[u8]>) -> Result<Vec<Vec<u8>>, AwebpToPngError> {
+fn encode_rgba_to_webp(
+    image: &image::RgbaImage,
+    lossless: bool,
+    quality: f32,
+) -> Result<Vec<u8>, AwebpToImageError> {
+    let width = image.width() as i32;
+    let height = image.height() as i32;
+    let stride = width * 4;
+
+    let mut output: *mut u8 = ptr::null_mut();
+    let size = unsafe {
+        if lossless {
+            sys::WebPEncodeLosslessRGBA(image.as_raw().as_ptr(), width, height, stride, &mut output)
+        } else {
+            sys::WebPEncodeRGBA(
+                image.as_raw().as_ptr(),
+                width,
+                height,
+                stride,
+                quality,
+                &mut output,
+            )
+        }
+    };
+    if output.is_null() || size == 0 {
+        return Err(AwebpToImageError::EncodeImageFailed);
+    }
+
+    let bytes = unsafe { slice::from_raw_parts(output, size) }.to_vec();
+    unsafe { sys::WebPFree(output as *mut _) };
+
+    Ok(bytes)
+}
+
+pub fn awebp_to_multi_png(
+    awebp_bytes: impl AsRef<[u8]>,
+    composite: impl Into<Option<AwebpCompositeOptions>>,
+) -> Result<Vec<Vec<u8>>, AwebpToPngError> {
+    let composite: AwebpCompositeOptions = composite.into().unwrap_or_default();
+
     let awebp_decoder =
         AwebPDecoder::new(awebp_bytes.as_ref()).map_err(|_| AwebpToPngError::DecodeAwebpFailed)?;
 
-    let awebp_decoder_iter = awebp_decoder.into_iter();
-
-    awebp_decoder_iter
+    let images = awebp_decoder
+        .into_iter()
         .map(|webp_frame| {
-            let image = webp_frame
+            webp_frame
                 .into_image()
-                .map_err(|_| AwebpToPngError::ToImageFailed)?;
+                .map_err(|_| AwebpToPngError::ToImageFailed)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    images
+        .into_iter()
+        .map(|image| {
+            let image = match composite.background_color_override {
+                Some(background_color) => apply_background_color_override(image, background_color),
+                None => image,
+            };
 
             let mut buf = Vec::with_capacity(image.as_bytes().len());
 
@@ -106,6 +205,189 @@ pub fn awebp_to_multi_png(awebp_bytes: impl AsRef<[u8]>) -> Result<Vec<Vec<u8>>,
         .collect::<Result<_, _>>()
 }
 
+//
+//
+//
+// `webp_animation::Decoder` wraps libwebp's `WebPAnimDecoder`, which already
+// composites each ANMF sub-frame onto the full canvas per its dispose/blend
+// flags and the container's background color before handing it back via
+// `Frame::into_image()`; there is no raw, uncomposited frame to reconstruct
+// here. The only thing callers can't get from the decoder is overriding what
+// a fully-transparent (disposed-to-background) pixel renders as, so that's
+// all this option does.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct AwebpCompositeOptions {
+    pub background_color_override: Option<Rgba<u8>>,
+}
+
+fn apply_background_color_override(
+    mut image: image::RgbaImage,
+    background_color: Rgba<u8>,
+) -> image::RgbaImage {
+    for pixel in image.pixels_mut() {
+        if pixel[3] == 0 {
+            *pixel = background_color;
+        }
+    }
+    image
+}
+
+//
+//
+//
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AwebpEncodeConfig {
+    pub lossless: bool,
+    pub quality: f32,
+    pub loop_count: u32,
+}
+impl Default for AwebpEncodeConfig {
+    fn default() -> Self {
+        Self {
+            lossless: false,
+            quality: 75.,
+            loop_count: 0,
+        }
+    }
+}
+
+pub fn pngs_to_awebp(
+    frames: impl IntoIterator<Item = (Vec<u8>, i32)>,
+    config: AwebpEncodeConfig,
+) -> Result<Vec<u8>, PngsToAwebpError> {
+    let frames: Vec<(Vec<u8>, i32)> = frames.into_iter().collect();
+    if frames.is_empty() {
+        return Err(PngsToAwebpError::FramesIsEmpty);
+    }
+
+    let mut canvas_frames = Vec::with_capacity(frames.len());
+    for (png_bytes, timestamp_ms) in frames {
+        let rgba_image = image::load_from_memory(&png_bytes)
+            .map_err(|_| PngsToAwebpError::DecodePngFailed)?
+            .into_rgba8();
+        canvas_frames.push((rgba_image, timestamp_ms));
+    }
+
+    let (canvas_width, canvas_height) = {
+        let (first, _) = &canvas_frames[0];
+        (first.width(), first.height())
+    };
+    if canvas_frames
+        .iter()
+        .any(|(image, _)| image.width() != canvas_width || image.height() != canvas_height)
+    {
+        return Err(PngsToAwebpError::CanvasSizeMismatch);
+    }
+    if canvas_frames
+        .windows(2)
+        .any(|w| w[1].1 <= w[0].1)
+    {
+        return Err(PngsToAwebpError::TimestampNotIncreasing);
+    }
+    if !(0. ..=100.).contains(&config.quality) {
+        return Err(PngsToAwebpError::QualityOutOfRange);
+    }
+
+    let mut webp_config: sys::WebPConfig = unsafe { std::mem::zeroed() };
+    if unsafe { sys::WebPConfigInit(&mut webp_config) } == 0 {
+        return Err(PngsToAwebpError::NewAwebpEncoderFailed);
+    }
+    webp_config.lossless = config.lossless as i32;
+    webp_config.quality = config.quality;
+
+    let mut enc_options: sys::WebPAnimEncoderOptions = unsafe { std::mem::zeroed() };
+    if unsafe { sys::WebPAnimEncoderOptionsInit(&mut enc_options) } == 0 {
+        return Err(PngsToAwebpError::NewAwebpEncoderFailed);
+    }
+    enc_options.anim_params.loop_count = config.loop_count as i32;
+
+    let encoder =
+        unsafe { sys::WebPAnimEncoderNew(canvas_width as i32, canvas_height as i32, &enc_options) };
+    if encoder.is_null() {
+        return Err(PngsToAwebpError::NewAwebpEncoderFailed);
+    }
+
+    let assembled = (|| -> Result<Vec<u8>, PngsToAwebpError> {
+        let mut last_timestamp_ms = 0;
+        for (rgba_image, timestamp_ms) in &canvas_frames {
+            let mut picture: sys::WebPPicture = unsafe { std::mem::zeroed() };
+            if unsafe { sys::WebPPictureInit(&mut picture) } == 0 {
+                return Err(PngsToAwebpError::AddAwebpFrameFailed);
+            }
+            picture.width = canvas_width as i32;
+            picture.height = canvas_height as i32;
+            picture.use_argb = 1;
+
+            let imported = unsafe {
+                sys::WebPPictureImportRGBA(
+                    &mut picture,
+                    rgba_image.as_raw().as_ptr(),
+                    canvas_width as i32 * 4,
+                )
+            };
+            if imported == 0 {
+                unsafe { sys::WebPPictureFree(&mut picture) };
+                return Err(PngsToAwebpError::AddAwebpFrameFailed);
+            }
+
+            let added = unsafe {
+                sys::WebPAnimEncoderAdd(encoder, &mut picture, *timestamp_ms, &webp_config)
+            };
+            unsafe { sys::WebPPictureFree(&mut picture) };
+            if added == 0 {
+                return Err(PngsToAwebpError::AddAwebpFrameFailed);
+            }
+
+            last_timestamp_ms = *timestamp_ms;
+        }
+
+        // The animation is only flushed once a sentinel frame (a null picture) is
+        // added with the timestamp marking the end of the last real frame. Only
+        // start timestamps are given, so the last frame is shown for 100 ms.
+        let end_timestamp_ms = last_timestamp_ms.saturating_add(100);
+        if unsafe {
+            sys::WebPAnimEncoderAdd(encoder, ptr::null_mut(), end_timestamp_ms, ptr::null())
+        } == 0
+        {
+            return Err(PngsToAwebpError::AddAwebpFrameFailed);
+        }
+
+        let mut webp_data: sys::WebPData = unsafe { std::mem::zeroed() };
+        if unsafe { sys::WebPAnimEncoderAssemble(encoder, &mut webp_data) } == 0 {
+            return Err(PngsToAwebpError::AssembleAwebpFailed);
+        }
+        let bytes = unsafe { slice::from_raw_parts(webp_data.bytes, webp_data.size) }.to_vec();
+        unsafe { sys::WebPDataClear(&mut webp_data) };
+
+        Ok(bytes)
+    })();
+
+    unsafe { sys::WebPAnimEncoderDelete(encoder) };
+
+    assembled
+}
+
+//
+//
+//
+#[derive(Debug)]
+pub enum PngsToAwebpError {
+    FramesIsEmpty,
+    DecodePngFailed,
+    CanvasSizeMismatch,
+    TimestampNotIncreasing,
+    QualityOutOfRange,
+    NewAwebpEncoderFailed,
+    AddAwebpFrameFailed,
+    AssembleAwebpFailed,
+}
+impl fmt::Display for PngsToAwebpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl error::Error for PngsToAwebpError {}
+
 //
 //
 //
@@ -123,13 +405,201 @@ impl fmt::Display for AwebpToPngError {
 }
 impl error::Error for AwebpToPngError {}
 
+//
+//
+//
+#[derive(Debug)]
+pub enum AwebpToImageError {
+    DecodeAwebpFailed,
+    AwebpSpecificFrameIsNone,
+    ToImageFailed,
+    QualityOutOfRange,
+    EncodeImageFailed,
+}
+impl fmt::Display for AwebpToImageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl error::Error for AwebpToImageError {}
+impl From<AwebpToImageError> for AwebpToPngError {
+    fn from(err: AwebpToImageError) -> Self {
+        match err {
+            AwebpToImageError::DecodeAwebpFailed => AwebpToPngError::DecodeAwebpFailed,
+            AwebpToImageError::AwebpSpecificFrameIsNone => AwebpToPngError::AwebpSpecificFrameIsNone,
+            AwebpToImageError::ToImageFailed => AwebpToPngError::ToImageFailed,
+            AwebpToImageError::QualityOutOfRange | AwebpToImageError::EncodeImageFailed => {
+                AwebpToPngError::EncodePngFailed
+            }
+        }
+    }
+}
+
+//
+//
+//
+pub struct AwebpTimedFrames {
+    pub frames: Vec<(Vec<u8>, u32)>,
+    pub loop_duration_ms: u32,
+}
+
+pub fn awebp_to_multi_png_with_timing(
+    awebp_bytes: impl AsRef<[u8]>,
+    last_frame_delay_ms_default: impl Into<Option<u32>>,
+) -> Result<AwebpTimedFrames, AwebpToPngError> {
+    let last_frame_delay_ms_default = last_frame_delay_ms_default.into().unwrap_or(100);
+
+    let (decoded_frames, loop_duration_ms) =
+        awebp_decode_frames_with_delay(awebp_bytes.as_ref(), last_frame_delay_ms_default)?;
+
+    let frames = decoded_frames
+        .into_iter()
+        .map(|(image, delay_ms)| {
+            let mut buf = Vec::with_capacity(image.as_bytes().len());
+
+            PngEncoder::new(&mut buf)
+                .write_image(
+                    image.as_bytes(),
+                    image.width(),
+                    image.height(),
+                    Rgba::<u8>::COLOR_TYPE,
+                )
+                .map_err(|_| AwebpToPngError::EncodePngFailed)?;
+
+            Ok((buf, delay_ms))
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(AwebpTimedFrames {
+        frames,
+        loop_duration_ms,
+    })
+}
+
+// The `webp_animation::Frame` timestamp is the presentation time of that
+// frame, not its delay, so a frame's delay is the gap to the next frame's
+// timestamp; the last frame has no "next" timestamp to diff against, hence
+// the caller-supplied default (mirrors `image::Delay` semantics for the
+// last frame of a GIF/APNG with no further presentation).
+fn awebp_decode_frames_with_delay(
+    awebp_bytes: &[u8],
+    last_frame_delay_ms_default: u32,
+) -> Result<(Vec<(image::RgbaImage, u32)>, u32), AwebpToPngError> {
+    let awebp_decoder =
+        AwebPDecoder::new(awebp_bytes).map_err(|_| AwebpToPngError::DecodeAwebpFailed)?;
+
+    let webp_frames: Vec<_> = awebp_decoder.into_iter().collect();
+    if webp_frames.is_empty() {
+        return Err(AwebpToPngError::AwebpSpecificFrameIsNone);
+    }
+
+    let mut timestamps_ms = Vec::with_capacity(webp_frames.len());
+    let mut images = Vec::with_capacity(webp_frames.len());
+    for webp_frame in webp_frames {
+        timestamps_ms.push(webp_frame.timestamp() as u32);
+        images.push(
+            webp_frame
+                .into_image()
+                .map_err(|_| AwebpToPngError::ToImageFailed)?,
+        );
+    }
+
+    let frames: Vec<_> = images
+        .into_iter()
+        .enumerate()
+        .map(|(i, image)| {
+            let delay_ms = match timestamps_ms.get(i + 1) {
+                Some(next_timestamp_ms) => next_timestamp_ms.saturating_sub(timestamps_ms[i]),
+                None => last_frame_delay_ms_default,
+            };
+
+            (image, delay_ms)
+        })
+        .collect();
+
+    let loop_duration_ms = timestamps_ms.last().copied().unwrap_or(0)
+        + frames.last().map(|(_, delay_ms)| *delay_ms).unwrap_or(0);
+
+    Ok((frames, loop_duration_ms))
+}
+
+//
+//
+//
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GifOptions {
+    pub loop_count: Option<u16>,
+    /// Encoding speed passed to `GifEncoder::new_with_speed`, 1 (slowest,
+    /// best quality) to 30 (fastest). Controls the NeuQuant color
+    /// quantization and dithering `image` applies per frame, since GIF is
+    /// paletted and WebP frames are not.
+    pub speed: i32,
+}
+impl Default for GifOptions {
+    fn default() -> Self {
+        Self {
+            loop_count: None,
+            speed: 10,
+        }
+    }
+}
+
+pub fn awebp_to_gif(
+    awebp_bytes: impl AsRef<[u8]>,
+    options: impl Into<Option<GifOptions>>,
+) -> Result<Vec<u8>, AwebpToGifError> {
+    let options = options.into().unwrap_or_default();
+
+    let (decoded_frames, _loop_duration_ms) =
+        awebp_decode_frames_with_delay(awebp_bytes.as_ref(), 100)
+            .map_err(AwebpToGifError::DecodeAwebpFailed)?;
+
+    let mut gif_bytes = Vec::new();
+    {
+        let mut gif_encoder = GifEncoder::new_with_speed(&mut gif_bytes, options.speed);
+
+        gif_encoder
+            .set_repeat(match options.loop_count {
+                Some(loop_count) => Repeat::Finite(loop_count),
+                None => Repeat::Infinite,
+            })
+            .map_err(|_| AwebpToGifError::EncodeGifFailed)?;
+
+        for (image, delay_ms) in decoded_frames {
+            let delay = Delay::from_saturating_duration(Duration::from_millis(delay_ms as u64));
+            let frame = Frame::from_parts(image, 0, 0, delay);
+
+            gif_encoder
+                .encode_frame(frame)
+                .map_err(|_| AwebpToGifError::EncodeGifFailed)?;
+        }
+    }
+
+    Ok(gif_bytes)
+}
+
+//
+//
+//
+#[derive(Debug)]
+pub enum AwebpToGifError {
+    DecodeAwebpFailed(AwebpToPngError),
+    EncodeGifFailed,
+}
+impl fmt::Display for AwebpToGifError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl error::Error for AwebpToGifError {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     use std::{
         fs::{self, File},
-        io::Write as _,
+        io::{Cursor, Write as _},
     };
 
     use tempfile::{tempdir, Builder};
@@ -137,7 +607,7 @@ mod tests {
     #[test]
     fn test_awebp_to_single_png_with_animated() {
         let awebp_bytes = include_bytes!("../tests/images/animated-webp-supported.webp");
-        let png_bytes = awebp_to_single_png(awebp_bytes, AwebpFramePosition::Last).unwrap();
+        let png_bytes = awebp_to_single_png(awebp_bytes, AwebpFramePosition::Last, None).unwrap();
 
         let png_decoder = png::Decoder::new(&png_bytes[..]);
         png_decoder.read_info().unwrap();
@@ -152,7 +622,7 @@ mod tests {
     #[test]
     fn test_awebp_to_multi_png_with_animated() {
         let awebp_bytes = include_bytes!("../tests/images/animated-webp-supported.webp");
-        let png_bytes_list = awebp_to_multi_png(awebp_bytes).unwrap();
+        let png_bytes_list = awebp_to_multi_png(awebp_bytes, None).unwrap();
 
         let tmp_dir = Builder::new()
             .prefix("animated-webp-supported")
@@ -177,7 +647,7 @@ mod tests {
     #[test]
     fn test_awebp_to_single_png_with_not_animated() {
         let awebp_bytes = include_bytes!("../tests/images/3_webp_ll.webp");
-        let png_bytes = awebp_to_single_png(awebp_bytes, None).unwrap();
+        let png_bytes = awebp_to_single_png(awebp_bytes, None, None).unwrap();
 
         let png_decoder = png::Decoder::new(&png_bytes[..]);
         png_decoder.read_info().unwrap();
@@ -192,7 +662,7 @@ mod tests {
     #[test]
     fn test_awebp_to_multi_png_with_not_animated() {
         let awebp_bytes = include_bytes!("../tests/images/3_webp_ll.webp");
-        let png_bytes_list = awebp_to_multi_png(awebp_bytes).unwrap();
+        let png_bytes_list = awebp_to_multi_png(awebp_bytes, None).unwrap();
 
         let tmp_dir = Builder::new().prefix("3_webp_ll").tempdir().unwrap();
 
@@ -210,4 +680,176 @@ mod tests {
             fs::read_dir(tmp_dir.path()).unwrap().collect::<Vec<_>>()
         );
     }
+
+    #[test]
+    fn test_awebp_to_multi_png_with_timing_with_animated() {
+        let awebp_bytes = include_bytes!("../tests/images/animated-webp-supported.webp");
+        let timed_frames = awebp_to_multi_png_with_timing(awebp_bytes, None).unwrap();
+
+        assert!(!timed_frames.frames.is_empty());
+        assert!(timed_frames.loop_duration_ms > 0);
+
+        for (png_bytes, _delay_ms) in timed_frames.frames {
+            let png_decoder = png::Decoder::new(&png_bytes[..]);
+            png_decoder.read_info().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_awebp_to_gif_with_animated() {
+        let awebp_bytes = include_bytes!("../tests/images/animated-webp-supported.webp");
+        let gif_bytes = awebp_to_gif(awebp_bytes, None).unwrap();
+
+        let tmp_dir = tempdir().unwrap();
+
+        let mut file = File::create(tmp_dir.path().join("animated-webp-supported.gif")).unwrap();
+        file.write_all(&gif_bytes[..]).unwrap();
+        file.sync_all().unwrap();
+    }
+
+    #[test]
+    fn test_awebp_to_gif_with_not_animated() {
+        let awebp_bytes = include_bytes!("../tests/images/3_webp_ll.webp");
+        let gif_bytes = awebp_to_gif(awebp_bytes, None).unwrap();
+
+        let tmp_dir = tempdir().unwrap();
+
+        let mut file = File::create(tmp_dir.path().join("3_webp_ll.gif")).unwrap();
+        file.write_all(&gif_bytes[..]).unwrap();
+        file.sync_all().unwrap();
+    }
+
+    #[test]
+    fn test_pngs_to_awebp_roundtrip() {
+        let mut frames = Vec::new();
+        for (i, timestamp_ms) in [(0, 0), (255, 100)] {
+            let mut image = image::RgbaImage::new(2, 2);
+            image.put_pixel(0, 0, Rgba([i, i, i, 255]));
+
+            let mut png_bytes = Vec::new();
+            DynamicImage::ImageRgba8(image)
+                .write_to(&mut Cursor::new(&mut png_bytes), image::ImageFormat::Png)
+                .unwrap();
+
+            frames.push((png_bytes, timestamp_ms));
+        }
+
+        let config = AwebpEncodeConfig {
+            loop_count: 3,
+            ..Default::default()
+        };
+        let awebp_bytes = pngs_to_awebp(frames, config).unwrap();
+
+        let awebp_decoder = AwebPDecoder::new(&awebp_bytes[..]).unwrap();
+        assert_eq!(awebp_decoder.into_iter().count(), 2);
+    }
+
+    #[test]
+    fn test_pngs_to_awebp_with_quality_out_of_range() {
+        let mut png_bytes = Vec::new();
+        DynamicImage::ImageRgba8(image::RgbaImage::new(2, 2))
+            .write_to(&mut Cursor::new(&mut png_bytes), image::ImageFormat::Png)
+            .unwrap();
+
+        let config = AwebpEncodeConfig {
+            quality: 150.,
+            ..Default::default()
+        };
+        let err = pngs_to_awebp([(png_bytes, 0)], config).unwrap_err();
+
+        assert!(matches!(err, PngsToAwebpError::QualityOutOfRange));
+    }
+
+    #[test]
+    fn test_apply_background_color_override_replaces_only_transparent_pixels() {
+        let mut image = image::RgbaImage::new(2, 1);
+        image.put_pixel(0, 0, Rgba([10, 20, 30, 0]));
+        image.put_pixel(1, 0, Rgba([10, 20, 30, 255]));
+
+        let background_color = Rgba([255, 0, 0, 255]);
+        let image = apply_background_color_override(image, background_color);
+
+        assert_eq!(*image.get_pixel(0, 0), background_color);
+        assert_eq!(*image.get_pixel(1, 0), Rgba([10, 20, 30, 255]));
+    }
+
+    #[test]
+    fn test_awebp_to_multi_png_with_background_color_override() {
+        let awebp_bytes = include_bytes!("../tests/images/animated-webp-supported.webp");
+
+        let composite = AwebpCompositeOptions {
+            background_color_override: Some(Rgba([255, 0, 0, 255])),
+        };
+        let png_bytes_list = awebp_to_multi_png(awebp_bytes, composite).unwrap();
+
+        let tmp_dir = Builder::new()
+            .prefix("animated-webp-supported-background-override")
+            .tempdir()
+            .unwrap();
+
+        for (i, png_bytes) in png_bytes_list.into_iter().enumerate() {
+            let png_decoder = png::Decoder::new(&png_bytes[..]);
+            png_decoder.read_info().unwrap();
+
+            let mut file = File::create(tmp_dir.path().join(format!("{}.png", i))).unwrap();
+            file.write_all(&png_bytes[..]).unwrap();
+            file.sync_all().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_awebp_to_image_with_jpeg() {
+        let awebp_bytes = include_bytes!("../tests/images/3_webp_ll.webp");
+        let jpeg_bytes = awebp_to_image(
+            awebp_bytes,
+            None,
+            None,
+            OutputFormat::Jpeg { quality: 80 },
+        )
+        .unwrap();
+
+        let tmp_dir = tempdir().unwrap();
+
+        let mut file = File::create(tmp_dir.path().join("3_webp_ll.jpg")).unwrap();
+        file.write_all(&jpeg_bytes[..]).unwrap();
+        file.sync_all().unwrap();
+    }
+
+    #[test]
+    fn test_awebp_to_image_with_webp() {
+        let awebp_bytes = include_bytes!("../tests/images/3_webp_ll.webp");
+        let webp_bytes = awebp_to_image(
+            awebp_bytes,
+            None,
+            None,
+            OutputFormat::WebP {
+                lossless: true,
+                quality: 75.,
+            },
+        )
+        .unwrap();
+
+        let tmp_dir = tempdir().unwrap();
+
+        let mut file = File::create(tmp_dir.path().join("3_webp_ll.webp")).unwrap();
+        file.write_all(&webp_bytes[..]).unwrap();
+        file.sync_all().unwrap();
+    }
+
+    #[test]
+    fn test_awebp_to_image_with_webp_quality_out_of_range() {
+        let awebp_bytes = include_bytes!("../tests/images/3_webp_ll.webp");
+        let err = awebp_to_image(
+            awebp_bytes,
+            None,
+            None,
+            OutputFormat::WebP {
+                lossless: false,
+                quality: 150.,
+            },
+        )
+        .unwrap_err();
+
+        assert!(matches!(err, AwebpToImageError::QualityOutOfRange));
+    }
 }